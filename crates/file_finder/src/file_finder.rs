@@ -1,17 +1,22 @@
 #[cfg(test)]
 mod file_finder_tests;
 
+mod file_finder_settings;
 mod new_path_prompt;
 
-use collections::{BTreeSet, HashMap};
+use collections::HashMap;
 use editor::{scroll::Autoscroll, Bias, Editor};
-use fuzzy::{CharBag, PathMatch, PathMatchCandidate};
+use file_finder_settings::FileFinderSettings;
+use futures::FutureExt;
+use fuzzy::{
+    CandidateBudget, CharBag, PartialPathMatches, PathMatch, PathMatchCandidate,
+    PathMatchCandidateSet as _,
+};
 use gpui::{
     actions, rems, Action, AnyElement, AppContext, DismissEvent, EventEmitter, FocusHandle,
     FocusableView, Model, Modifiers, ModifiersChangedEvent, ParentElement, Render, Styled, Task,
     View, ViewContext, VisualContext, WeakView,
 };
-use itertools::Itertools;
 use new_path_prompt::NewPathPrompt;
 use picker::{Picker, PickerDelegate};
 use project::{PathMatchCandidateSet, Project, ProjectPath, WorktreeId};
@@ -23,10 +28,14 @@ use std::{
         atomic::{self, AtomicBool},
         Arc,
     },
+    time::Duration,
 };
 use text::Point;
-use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
-use util::{paths::PathLikeWithPosition, post_inc, ResultExt};
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing, Tooltip};
+use util::{
+    paths::{normalize_path_for_display, PathLikeWithPosition},
+    post_inc, ResultExt,
+};
 use workspace::{item::PreviewTabsSettings, ModalView, Workspace};
 
 actions!(file_finder, [SelectPrev]);
@@ -39,6 +48,7 @@ pub struct FileFinder {
 }
 
 pub fn init(cx: &mut AppContext) {
+    FileFinderSettings::register(cx);
     cx.observe_new_views(FileFinder::register).detach();
     cx.observe_new_views(NewPathPrompt::register).detach();
 }
@@ -129,6 +139,36 @@ impl FileFinder {
     }
 }
 
+#[cfg(any(test, feature = "test-support"))]
+impl FileFinder {
+    /// Searches for the given query, returning a task that resolves once its matches are set.
+    pub fn update_query(&mut self, query: &str, cx: &mut ViewContext<Self>) -> Task<()> {
+        self.picker.update(cx, |picker, cx| {
+            picker.delegate.update_matches(query.to_string(), cx)
+        })
+    }
+
+    /// Returns the file name and the directory labels of the current matches, as they are rendered.
+    pub fn match_labels(&self, cx: &AppContext) -> Vec<(String, String)> {
+        let delegate = &self.picker.read(cx).delegate;
+        delegate
+            .matches
+            .matches
+            .iter()
+            .enumerate()
+            .map(|(ix, path_match)| {
+                let (file_name, _, directory, _, _) =
+                    delegate.rendered_labels_for_match(path_match, cx, ix);
+                (file_name, directory)
+            })
+            .collect()
+    }
+
+    pub fn selected_index(&self, cx: &AppContext) -> usize {
+        self.picker.read(cx).delegate.selected_index
+    }
+}
+
 impl EventEmitter<DismissEvent> for FileFinder {}
 
 impl FocusableView for FileFinder {
@@ -141,7 +181,7 @@ impl Render for FileFinder {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         v_flex()
             .key_context("FileFinder")
-            .w(rems(34.))
+            .w(rems(FileFinderSettings::get_global(cx).modal_width))
             .on_modifiers_changed(cx.listener(Self::handle_modifiers_changed))
             .on_action(cx.listener(Self::handle_select_prev))
             .child(self.picker.clone())
@@ -156,6 +196,7 @@ pub struct FileFinderDelegate {
     latest_search_id: usize,
     latest_search_did_cancel: bool,
     latest_search_query: Option<PathLikeWithPosition<FileSearchQuery>>,
+    latest_search_incomplete: bool,
     currently_opened_path: Option<FoundPath>,
     matches: Matches,
     selected_index: usize,
@@ -201,6 +242,7 @@ impl PartialOrd for ProjectPanelOrdMatch {
 #[derive(Debug, Default)]
 struct Matches {
     separate_history: bool,
+    max_results: usize,
     matches: Vec<Match>,
 }
 
@@ -210,6 +252,18 @@ enum Match {
     Search(ProjectPanelOrdMatch),
 }
 
+impl Match {
+    fn worktree_and_path(&self) -> (usize, &Arc<Path>) {
+        match self {
+            Match::History(found_path, _) => (
+                found_path.project.worktree_id.to_usize(),
+                &found_path.project.path,
+            ),
+            Match::Search(path_match) => (path_match.0.worktree_id, &path_match.0.path),
+        }
+    }
+}
+
 impl Matches {
     fn len(&self) -> usize {
         self.matches.len()
@@ -256,12 +310,13 @@ impl Matches {
                 Some((i + 1, Match::History(history_item.clone(), query_match)))
             });
 
-        let mut unique_matches = BTreeSet::new();
-        self.matches = old_search_matches
-            .chain(history_matches)
-            .chain(new_search_matches)
-            .filter(|(_, m)| unique_matches.insert(m.clone()))
-            .sorted_by(|(history_score_a, a), (history_score_b, b)| {
+        self.matches = fuzzy::merge_ranked(
+            old_search_matches
+                .chain(history_matches)
+                .chain(new_search_matches),
+            self.max_results,
+            |(_, m)| m.clone(),
+            |(history_score_a, a), (history_score_b, b)| {
                 match (a, b) {
                     // bubble currently opened files to the top
                     (Match::History(path, _), _) if Some(path) == currently_opened => {
@@ -290,10 +345,11 @@ impl Matches {
                     (Match::Search(match_a), Match::Search(match_b)) => match_b.cmp(match_a),
                 }
                 .then(history_score_a.cmp(history_score_b))
-            })
-            .take(100)
-            .map(|(_, m)| m)
-            .collect();
+            },
+        )
+        .into_iter()
+        .map(|(_, m)| m)
+        .collect();
     }
 }
 
@@ -316,6 +372,7 @@ fn matching_history_item_paths<'a>(
         .filter_map(|found_path| {
             let candidate = PathMatchCandidate {
                 path: &found_path.project.path,
+                lowercase_path: None,
                 // Only match history items names, otherwise their paths may match too many queries, producing false positives.
                 // E.g. `foo` would match both `something/foo/bar.rs` and `something/foo/foo.rs` and if the former is a history item,
                 // it would be shown first always, despite the latter being a better match.
@@ -377,6 +434,17 @@ impl FoundPath {
 }
 
 const MAX_RECENT_SELECTIONS: usize = 20;
+/// Number of directory label characters shown per rem of the modal width, leaving room for the file name.
+/// At the default width of 34 rems, directories longer than 40 characters are truncated.
+const DIRECTORY_LABEL_LEN_PER_REM: f32 = 1.2;
+/// How often the matches found so far are shown while a search is running.
+const PARTIAL_MATCHES_POLL_INTERVAL: Duration = Duration::from_millis(30);
+/// Searches over fewer paths finish before their partial matches could be seen, so they aren't streamed.
+const MIN_PATHS_TO_STREAM_MATCHES: usize = 10_000;
+
+fn max_directory_label_len(cx: &AppContext) -> usize {
+    (FileFinderSettings::get_global(cx).modal_width * DIRECTORY_LABEL_LEN_PER_REM) as usize
+}
 
 #[cfg(not(test))]
 fn history_file_exists(abs_path: &PathBuf) -> bool {
@@ -408,6 +476,37 @@ impl FileSearchQuery {
     }
 }
 
+/// Worktree files to search for a query, optionally limited to a number of files
+/// that contain all of the query's characters.
+struct BudgetedCandidateSet {
+    candidates: PathMatchCandidateSet,
+    budget: Option<CandidateBudget>,
+}
+
+impl<'a> fuzzy::PathMatchCandidateSet<'a> for BudgetedCandidateSet {
+    type Candidates = project::PathMatchCandidateSetIter<'a>;
+
+    fn id(&self) -> usize {
+        self.candidates.id()
+    }
+
+    fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    fn prefix(&self) -> Arc<str> {
+        self.candidates.prefix()
+    }
+
+    fn candidates(&'a self, start: usize) -> Self::Candidates {
+        self.candidates.candidates(start)
+    }
+
+    fn budget(&self) -> Option<&CandidateBudget> {
+        self.budget.as_ref()
+    }
+}
+
 impl FileFinderDelegate {
     fn new(
         file_finder: WeakView<FileFinder>,
@@ -427,8 +526,12 @@ impl FileFinderDelegate {
             latest_search_id: 0,
             latest_search_did_cancel: false,
             latest_search_query: None,
+            latest_search_incomplete: false,
             currently_opened_path,
-            matches: Matches::default(),
+            matches: Matches {
+                max_results: FileFinderSettings::get_global(cx).max_results,
+                ..Matches::default()
+            },
             has_changed_selected_index: false,
             selected_index: 0,
             cancel_flag: Arc::new(AtomicBool::new(false)),
@@ -466,44 +569,111 @@ impl FileFinderDelegate {
             .visible_worktrees(cx)
             .collect::<Vec<_>>();
         let include_root_name = worktrees.len() > 1;
+        let settings = FileFinderSettings::get_global(cx);
+        let max_results = settings.max_results;
         let candidate_sets = worktrees
             .into_iter()
             .map(|worktree| {
                 let worktree = worktree.read(cx);
-                PathMatchCandidateSet {
-                    snapshot: worktree.snapshot(),
-                    include_ignored: worktree
-                        .root_entry()
-                        .map_or(false, |entry| entry.is_ignored),
-                    include_root_name,
-                    candidates: project::Candidates::Files,
+                BudgetedCandidateSet {
+                    candidates: PathMatchCandidateSet {
+                        snapshot: worktree.snapshot(),
+                        include_ignored: worktree
+                            .root_entry()
+                            .map_or(false, |entry| entry.is_ignored),
+                        include_root_name,
+                        candidates: project::Candidates::Files,
+                    },
+                    budget: settings.max_files_per_worktree.map(CandidateBudget::new),
                 }
             })
             .collect::<Vec<_>>();
+        let path_count = candidate_sets
+            .iter()
+            .map(|candidate_set| candidate_set.len())
+            .sum::<usize>();
 
         let search_id = util::post_inc(&mut self.search_count);
         self.cancel_flag.store(true, atomic::Ordering::Relaxed);
         self.cancel_flag = Arc::new(AtomicBool::new(false));
         let cancel_flag = self.cancel_flag.clone();
         cx.spawn(|picker, mut cx| async move {
-            let matches = fuzzy::match_path_sets(
-                candidate_sets.as_slice(),
-                query.path_like.path_query(),
-                relative_to,
-                false,
-                100,
-                &cancel_flag,
-                cx.background_executor().clone(),
-            )
-            .await
+            let matches = if path_count < MIN_PATHS_TO_STREAM_MATCHES {
+                fuzzy::match_path_sets(
+                    candidate_sets.as_slice(),
+                    query.path_like.path_query(),
+                    relative_to,
+                    false,
+                    max_results,
+                    &cancel_flag,
+                    cx.background_executor().clone(),
+                )
+                .await
+            } else {
+                let partial_matches = PartialPathMatches::default();
+                let search = fuzzy::match_path_sets_incremental(
+                    candidate_sets.as_slice(),
+                    query.path_like.path_query(),
+                    relative_to,
+                    false,
+                    max_results,
+                    &cancel_flag,
+                    &partial_matches,
+                    cx.background_executor().clone(),
+                )
+                .fuse();
+                futures::pin_mut!(search);
+
+                // Show the best matches found so far while the search is still running on large projects.
+                let mut shown_version = 0;
+                loop {
+                    let mut timer = cx
+                        .background_executor()
+                        .timer(PARTIAL_MATCHES_POLL_INTERVAL)
+                        .fuse();
+                    futures::select_biased! {
+                        matches = search => break matches,
+                        _ = timer => {
+                            let version = partial_matches.version();
+                            if version == shown_version
+                                || cancel_flag.load(atomic::Ordering::Relaxed)
+                            {
+                                continue;
+                            }
+                            shown_version = version;
+                            let matches = partial_matches
+                                .top_matches(max_results)
+                                .into_iter()
+                                .map(ProjectPanelOrdMatch);
+                            let query = query.clone();
+                            picker
+                                .update(&mut cx, |picker, cx| {
+                                    picker
+                                        .delegate
+                                        .set_partial_matches(search_id, query, matches, cx)
+                                })
+                                .log_err();
+                        }
+                    }
+                }
+            }
             .into_iter()
             .map(ProjectPanelOrdMatch);
             let did_cancel = cancel_flag.load(atomic::Ordering::Relaxed);
+            let budget_exceeded = candidate_sets.iter().any(|candidate_set| {
+                candidate_set
+                    .budget
+                    .as_ref()
+                    .map_or(false, |budget| budget.is_exceeded())
+            });
             picker
                 .update(&mut cx, |picker, cx| {
                     picker
                         .delegate
-                        .set_search_matches(search_id, did_cancel, query, matches, cx)
+                        .set_search_matches(search_id, did_cancel, query, matches, cx);
+                    if picker.delegate.latest_search_id == search_id {
+                        picker.delegate.latest_search_incomplete = budget_exceeded;
+                    }
                 })
                 .log_err();
         })
@@ -518,13 +688,24 @@ impl FileFinderDelegate {
         cx: &mut ViewContext<Picker<Self>>,
     ) {
         if search_id >= self.latest_search_id {
+            let query_changed = Some(query.path_like.path_query())
+                != self
+                    .latest_search_query
+                    .as_ref()
+                    .map(|query| query.path_like.path_query());
+            // The search was cancelled by a newer one, which will report its own matches soon:
+            // keep showing the previous ones instead of flashing the partial results for another query.
+            if did_cancel && query_changed {
+                return;
+            }
+            // Partial matches of this search are shown already: keep the match the user may have selected since.
+            let selected_path = if search_id == self.latest_search_id && !query_changed {
+                self.selected_path()
+            } else {
+                None
+            };
             self.latest_search_id = search_id;
-            let extend_old_matches = self.latest_search_did_cancel
-                && Some(query.path_like.path_query())
-                    == self
-                        .latest_search_query
-                        .as_ref()
-                        .map(|query| query.path_like.path_query());
+            let extend_old_matches = self.latest_search_did_cancel && !query_changed;
             self.matches.push_new_matches(
                 &self.history_items,
                 self.currently_opened_path.as_ref(),
@@ -534,11 +715,96 @@ impl FileFinderDelegate {
             );
             self.latest_search_query = Some(query);
             self.latest_search_did_cancel = did_cancel;
-            self.selected_index = self.calculate_selected_index();
+            self.latest_search_incomplete = false;
+            self.select_path(selected_path);
+            cx.notify();
+        }
+    }
+
+    /// Shows the best matches found so far by a search that is still running.
+    /// Unlike [`Self::set_search_matches`], keeps the selected match once matches for the query are shown,
+    /// and leaves the cancellation state of the previous search to the final report.
+    fn set_partial_matches(
+        &mut self,
+        search_id: usize,
+        query: PathLikeWithPosition<FileSearchQuery>,
+        matches: impl IntoIterator<Item = ProjectPanelOrdMatch>,
+        cx: &mut ViewContext<Picker<Self>>,
+    ) {
+        if search_id >= self.latest_search_id {
+            let query_changed = Some(query.path_like.path_query())
+                != self
+                    .latest_search_query
+                    .as_ref()
+                    .map(|query| query.path_like.path_query());
+            let selected_path = if query_changed {
+                None
+            } else {
+                self.selected_path()
+            };
+            self.latest_search_id = search_id;
+            self.matches.push_new_matches(
+                &self.history_items,
+                self.currently_opened_path.as_ref(),
+                Some(&query),
+                matches.into_iter(),
+                false,
+            );
+            self.latest_search_query = Some(query);
+            self.select_path(selected_path);
             cx.notify();
         }
     }
 
+    fn selected_path(&self) -> Option<(usize, Arc<Path>)> {
+        let (worktree_id, path) = self.matches.get(self.selected_index)?.worktree_and_path();
+        Some((worktree_id, Arc::clone(path)))
+    }
+
+    /// Selects the match for `path` wherever it moved to, or the default match if it's no longer shown.
+    fn select_path(&mut self, path: Option<(usize, Arc<Path>)>) {
+        self.selected_index = path
+            .and_then(|(worktree_id, path)| {
+                self.matches
+                    .matches
+                    .iter()
+                    .position(|m| m.worktree_and_path() == (worktree_id, &path))
+            })
+            .unwrap_or_else(|| self.calculate_selected_index());
+    }
+
+    /// Returns the labels of a match as they are rendered: its file name and directory,
+    /// the latter shortened to fit the modal, along with the full path to show on hover if it was shortened.
+    fn rendered_labels_for_match(
+        &self,
+        path_match: &Match,
+        cx: &AppContext,
+        ix: usize,
+    ) -> (String, Vec<usize>, String, Vec<usize>, Option<String>) {
+        let (file_name, file_name_positions, full_path, full_path_positions) =
+            self.labels_for_match(path_match, cx, ix);
+        let (directory, directory_positions) = truncate_path_middle(
+            &full_path,
+            &full_path_positions,
+            max_directory_label_len(cx),
+        );
+        let tooltip = (directory != full_path).then(|| {
+            // History items outside of the project are labeled with their whole absolute path already.
+            if full_path.ends_with(&file_name) {
+                full_path
+            } else {
+                format!("{full_path}{file_name}")
+            }
+        });
+        (
+            file_name,
+            file_name_positions,
+            directory,
+            directory_positions,
+            tooltip,
+        )
+    }
+
     fn labels_for_match(
         &self,
         path_match: &Match,
@@ -566,7 +832,8 @@ impl FileFinderDelegate {
                                 )
                                 .to_string(),
                             Vec::new(),
-                            absolute_path.to_string_lossy().to_string(),
+                            normalize_path_for_display(&absolute_path.to_string_lossy())
+                                .into_owned(),
                             Vec::new(),
                         );
                     }
@@ -597,16 +864,24 @@ impl FileFinderDelegate {
             }
             Match::Search(path_match) => self.labels_for_path_match(&path_match.0),
         };
+        // Normalization keeps the byte length of the path, so the match positions stay valid.
+        let full_path = normalize_path_for_display(&full_path).into_owned();
 
         if file_name_positions.is_empty() {
             if let Some(user_home_path) = std::env::var("HOME").ok() {
                 let user_home_path = user_home_path.trim();
                 if !user_home_path.is_empty() {
-                    if (&full_path).starts_with(user_home_path) {
+                    if let Some(path_in_home) = full_path.strip_prefix(user_home_path) {
+                        let home_len = user_home_path.len();
+                        let full_path_positions = full_path_positions
+                            .into_iter()
+                            .filter(|&position| position >= home_len)
+                            .map(|position| position - home_len + "~".len())
+                            .collect();
                         return (
                             file_name,
                             file_name_positions,
-                            full_path.replace(user_home_path, "~"),
+                            format!("~{path_in_home}"),
                             full_path_positions,
                         );
                     }
@@ -622,6 +897,8 @@ impl FileFinderDelegate {
         )
     }
 
+    /// Splits the path of a match into its file name and directory labels,
+    /// distributing the match positions (UTF-8 byte offsets into the full path) between them.
     fn labels_for_path_match(
         &self,
         path_match: &PathMatch,
@@ -629,28 +906,37 @@ impl FileFinderDelegate {
         let path = &path_match.path;
         let path_string = path.to_string_lossy();
         let full_path = [path_match.path_prefix.as_ref(), path_string.as_ref()].join("");
-        let mut path_positions = path_match.positions.clone();
 
         let file_name = path.file_name().map_or_else(
             || path_match.path_prefix.to_string(),
             |file_name| file_name.to_string_lossy().to_string(),
         );
-        let file_name_start = path_match.path_prefix.len() + path_string.len() - file_name.len();
-        let file_name_positions = path_positions
-            .iter()
-            .filter_map(|pos| {
-                if pos >= &file_name_start {
-                    Some(pos - file_name_start)
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let file_name_start = if full_path.ends_with(&file_name) {
+            full_path.len() - file_name.len()
+        } else {
+            full_path.len()
+        };
 
-        let full_path = full_path.trim_end_matches(&file_name).to_string();
-        path_positions.retain(|idx| *idx < full_path.len());
+        let mut file_name_positions = Vec::new();
+        let mut directory_positions = Vec::new();
+        for &position in &path_match.positions {
+            if !full_path.is_char_boundary(position) || position >= full_path.len() {
+                continue;
+            }
+            if position >= file_name_start {
+                file_name_positions.push(position - file_name_start);
+            } else {
+                directory_positions.push(position);
+            }
+        }
 
-        (file_name, file_name_positions, full_path, path_positions)
+        let directory = full_path[..file_name_start].to_string();
+        (
+            file_name,
+            file_name_positions,
+            directory,
+            directory_positions,
+        )
     }
 
     fn lookup_absolute_path(
@@ -673,24 +959,23 @@ impl FileFinderDelegate {
             let mut path_matches = Vec::new();
             match fs.metadata(query_path).await.log_err() {
                 Some(Some(_metadata)) => {
-                    let update_result = project
+                    let Some(resolve_task) = project
                         .update(&mut cx, |project, cx| {
-                            if let Some((worktree, relative_path)) =
-                                project.find_local_worktree(query_path, cx)
-                            {
-                                path_matches.push(ProjectPanelOrdMatch(PathMatch {
-                                    score: 1.0,
-                                    positions: Vec::new(),
-                                    worktree_id: worktree.read(cx).id().to_usize(),
-                                    path: Arc::from(relative_path),
-                                    path_prefix: "".into(),
-                                    distance_to_relative_ancestor: usize::MAX,
-                                }));
-                            }
+                            project.resolve_abs_file_path(query_path, cx)
                         })
-                        .log_err();
-                    if update_result.is_none() {
+                        .log_err()
+                    else {
                         return;
+                    };
+                    if let Some(project_path) = resolve_task.await {
+                        path_matches.push(ProjectPanelOrdMatch(PathMatch {
+                            score: 1.0,
+                            positions: Vec::new(),
+                            worktree_id: project_path.worktree_id.to_usize(),
+                            path: project_path.path,
+                            path_prefix: "".into(),
+                            distance_to_relative_ancestor: usize::MAX,
+                        }));
                     }
                 }
                 Some(None) => {}
@@ -723,6 +1008,59 @@ impl FileFinderDelegate {
     }
 }
 
+/// Shortens a directory label longer than `max_len` characters by replacing its middle components with `…`,
+/// keeping the first component and as many trailing ones as fit, e.g. `crates/…/src/`.
+/// Highlight positions are remapped onto the shortened label, the ones inside the cut out part are dropped.
+fn truncate_path_middle(path: &str, positions: &[usize], max_len: usize) -> (String, Vec<usize>) {
+    const ELLIPSIS: &str = "…";
+    if path.chars().count() <= max_len {
+        return (path.to_string(), positions.to_vec());
+    }
+
+    let separator = std::path::MAIN_SEPARATOR;
+    let component_starts = path
+        .match_indices(separator)
+        .map(|(ix, _)| ix + separator.len_utf8())
+        // Keep the leading separator of absolute paths together with their first component.
+        .filter(|&ix| ix > separator.len_utf8() && ix < path.len())
+        .collect::<Vec<_>>();
+    let (&prefix_end, suffix_starts) = match component_starts.as_slice() {
+        [prefix_end, suffix_starts @ ..] if !suffix_starts.is_empty() => {
+            (prefix_end, suffix_starts)
+        }
+        _ => return (path.to_string(), positions.to_vec()),
+    };
+    let shortest_suffix_start = suffix_starts[suffix_starts.len() - 1];
+
+    let prefix_len = path[..prefix_end].chars().count() + 2;
+    let suffix_start = suffix_starts
+        .iter()
+        .rev()
+        .copied()
+        .take_while(|&start| prefix_len + path[start..].chars().count() <= max_len)
+        .last()
+        .unwrap_or(shortest_suffix_start);
+
+    let truncated = format!(
+        "{}{ELLIPSIS}{separator}{}",
+        &path[..prefix_end],
+        &path[suffix_start..]
+    );
+    let truncated_positions = positions
+        .iter()
+        .filter_map(|&position| {
+            if position < prefix_end {
+                Some(position)
+            } else if position >= suffix_start {
+                Some(position - suffix_start + prefix_end + ELLIPSIS.len() + separator.len_utf8())
+            } else {
+                None
+            }
+        })
+        .collect();
+    (truncated, truncated_positions)
+}
+
 impl PickerDelegate for FileFinderDelegate {
     type ListItem = ListItem;
 
@@ -772,8 +1110,10 @@ impl PickerDelegate for FileFinderDelegate {
         if raw_query.is_empty() {
             let project = self.project.read(cx);
             self.latest_search_id = post_inc(&mut self.search_count);
+            self.latest_search_incomplete = false;
             self.matches = Matches {
                 separate_history: self.separate_history,
+                max_results: FileFinderSettings::get_global(cx).max_results,
                 ..Matches::default()
             };
             self.matches.push_new_matches(
@@ -959,8 +1299,8 @@ impl PickerDelegate for FileFinderDelegate {
                 .size(IconSize::Small.rems())
                 .into_any_element(),
         };
-        let (file_name, file_name_positions, full_path, full_path_positions) =
-            self.labels_for_match(path_match, cx, ix);
+        let (file_name, file_name_positions, directory, directory_positions, tooltip) =
+            self.rendered_labels_for_match(path_match, cx, ix);
 
         Some(
             ListItem::new(ix)
@@ -968,19 +1308,42 @@ impl PickerDelegate for FileFinderDelegate {
                 .end_slot::<AnyElement>(Some(icon))
                 .inset(true)
                 .selected(selected)
+                .when_some(tooltip, |list_item, tooltip| {
+                    list_item.tooltip(move |cx| Tooltip::text(tooltip.clone(), cx))
+                })
                 .child(
                     h_flex()
                         .gap_2()
                         .py_px()
                         .child(HighlightedLabel::new(file_name, file_name_positions))
                         .child(
-                            HighlightedLabel::new(full_path, full_path_positions)
+                            HighlightedLabel::new(directory, directory_positions)
                                 .size(LabelSize::Small)
                                 .color(Color::Muted),
                         ),
                 ),
         )
     }
+
+    fn render_footer(&self, cx: &mut ViewContext<Picker<Self>>) -> Option<AnyElement> {
+        if !self.latest_search_incomplete {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .border_t_1()
+                .py_2()
+                .px_3()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Label::new("Results may be incomplete — refine your query")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -1079,4 +1442,35 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_truncate_path_middle() {
+        let separator = std::path::MAIN_SEPARATOR_STR;
+        let path = ["crates", "file_finder", "src", "deep", "module", ""].join(separator);
+
+        assert_eq!(
+            truncate_path_middle(&path, &[0, 7], 100),
+            (path.clone(), vec![0, 7]),
+            "Paths that fit should not be truncated"
+        );
+        assert_eq!(
+            truncate_path_middle(&path, &[0, 7, 25, 30], 21),
+            (
+                ["crates", "…", "deep", "module", ""].join(separator),
+                vec![0, 13, 18]
+            ),
+            "Middle components should be replaced, dropping their highlight positions"
+        );
+        assert_eq!(
+            truncate_path_middle(&path, &[0, 30], 5),
+            (["crates", "…", "module", ""].join(separator), vec![0, 13]),
+            "First and last components should be kept even if they do not fit"
+        );
+
+        let unicode_path = ["ünïcödé", "dir", "another", "läst", ""].join(separator);
+        assert_eq!(
+            truncate_path_middle(&unicode_path, &[0, 2, 29], 12),
+            (["ünïcödé", "…", "läst", ""].join(separator), vec![0, 2, 21]),
+        );
+    }
 }
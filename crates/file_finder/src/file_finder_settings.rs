@@ -0,0 +1,42 @@
+use anyhow;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct FileFinderSettings {
+    pub modal_width: f32,
+    pub max_results: usize,
+    pub max_files_per_worktree: Option<usize>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct FileFinderSettingsContent {
+    /// Width of the file finder modal, in rems.
+    ///
+    /// Default: 34
+    pub modal_width: Option<f32>,
+    /// Maximum number of matches shown in the file finder.
+    ///
+    /// Default: 100
+    pub max_results: Option<usize>,
+    /// Maximum number of files in each worktree that are matched against a query, if any.
+    /// Only files containing all of the query's characters count towards it,
+    /// so refining the query lets more of the worktree be searched.
+    ///
+    /// Default: null
+    pub max_files_per_worktree: Option<usize>,
+}
+
+impl Settings for FileFinderSettings {
+    const KEY: Option<&'static str> = Some("file_finder");
+
+    type FileContent = FileFinderSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
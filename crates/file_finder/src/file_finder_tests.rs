@@ -6,6 +6,7 @@ use gpui::{Entity, TestAppContext, VisualTestContext};
 use menu::{Confirm, SelectNext, SelectPrev};
 use project::FS_WATCH_LATENCY;
 use serde_json::json;
+use settings::SettingsStore;
 use workspace::{AppState, ToggleFileFinder, Workspace};
 
 #[ctor::ctor]
@@ -83,6 +84,218 @@ async fn test_matching_paths(cx: &mut TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_max_results_setting(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<FileFinderSettings>(cx, |settings| {
+                settings.max_results = Some(2);
+            });
+        })
+    });
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/root",
+            json!({
+                "a": {
+                    "banana": "",
+                    "bandana": "",
+                    "band": "",
+                }
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+
+    let (picker, _, cx) = build_find_picker(project, cx);
+
+    cx.simulate_input("ban");
+    picker.update(cx, |picker, _| {
+        assert_eq!(
+            picker.delegate.matches.len(),
+            2,
+            "Matches should be capped by the max_results setting"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_zero_max_results_setting(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<FileFinderSettings>(cx, |settings| {
+                settings.max_results = Some(0);
+            });
+        })
+    });
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/root",
+            json!({
+                "a": {
+                    "banana": "",
+                    "bandana": "",
+                }
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+
+    let (picker, _, cx) = build_find_picker(project, cx);
+
+    cx.simulate_input("ban");
+    picker.update(cx, |picker, _| {
+        assert_eq!(
+            picker.delegate.matches.len(),
+            0,
+            "No matches should be shown when max_results is 0"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_max_files_per_worktree_setting(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<FileFinderSettings>(cx, |settings| {
+                settings.max_files_per_worktree = Some(2);
+            });
+        })
+    });
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/root",
+            json!({
+                "a": {
+                    "banana": "",
+                    "bandana": "",
+                    "band": "",
+                    "cherry": "",
+                }
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+
+    let (picker, _, cx) = build_find_picker(project, cx);
+
+    cx.simulate_input("ban");
+    picker.update(cx, |picker, _| {
+        assert_eq!(
+            picker.delegate.matches.len(),
+            2,
+            "Only files within the budget should be matched"
+        );
+        assert!(picker.delegate.latest_search_incomplete);
+    });
+
+    cx.simulate_input("dana");
+    picker.update(cx, |picker, _| {
+        assert_eq!(
+            collect_search_matches(picker).search_paths_only(),
+            vec![PathBuf::from("a/bandana")],
+        );
+        assert!(
+            !picker.delegate.latest_search_incomplete,
+            "Files without the query characters should not count towards the budget"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_match_labels(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/root",
+            json!({
+                "crates": {
+                    "file_finder": {
+                        "src": {
+                            "nested": {
+                                "directories": {
+                                    "file_finder.rs": "",
+                                }
+                            }
+                        }
+                    },
+                    "picker": {
+                        "picker.rs": "",
+                    }
+                }
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+    let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+    open_file_picker(&workspace, cx);
+    let file_finder = workspace.update(cx, |workspace, cx| {
+        workspace.active_modal::<FileFinder>(cx).unwrap()
+    });
+
+    file_finder
+        .update(cx, |file_finder, cx| file_finder.update_query("picker", cx))
+        .await;
+    file_finder.update(cx, |file_finder, cx| {
+        assert_eq!(
+            file_finder.match_labels(cx),
+            vec![(
+                "picker.rs".to_string(),
+                format!("crates{0}picker{0}", std::path::MAIN_SEPARATOR)
+            )]
+        );
+        assert_eq!(file_finder.selected_index(cx), 0);
+    });
+
+    file_finder
+        .update(cx, |file_finder, cx| {
+            file_finder.update_query("finder.rs", cx)
+        })
+        .await;
+    file_finder.update(cx, |file_finder, cx| {
+        assert_eq!(
+            file_finder.match_labels(cx),
+            vec![(
+                "file_finder.rs".to_string(),
+                format!(
+                    "crates{0}…{0}src{0}nested{0}directories{0}",
+                    std::path::MAIN_SEPARATOR
+                )
+            )],
+            "Long directories should be truncated in the middle"
+        );
+
+        let picker = file_finder.picker.read(cx);
+        let (_, _, _, _, tooltip) =
+            picker
+                .delegate
+                .rendered_labels_for_match(&picker.delegate.matches.matches[0], cx, 0);
+        assert_eq!(
+            tooltip,
+            Some(format!(
+                "crates{0}file_finder{0}src{0}nested{0}directories{0}file_finder.rs",
+                std::path::MAIN_SEPARATOR
+            )),
+            "Truncated labels should show the full path of the file on hover"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_absolute_paths(cx: &mut TestAppContext) {
     let app_state = init_test(cx);
@@ -145,6 +358,55 @@ async fn test_absolute_paths(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_absolute_symlinked_paths(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/root",
+            json!({
+                "a": {
+                    "b": {
+                        "file2.txt": "",
+                    },
+                }
+            }),
+        )
+        .await;
+    app_state
+        .fs
+        .as_fake()
+        .insert_symlink("/linked", PathBuf::from("/root/a"))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+
+    let (picker, workspace, cx) = build_find_picker(project, cx);
+
+    picker
+        .update(cx, |picker, cx| {
+            picker
+                .delegate
+                .update_matches("/linked/b/file2.txt".to_string(), cx)
+        })
+        .await;
+    picker.update(cx, |picker, _| {
+        assert_eq!(
+            collect_search_matches(picker).search_paths_only(),
+            vec![PathBuf::from("a/b/file2.txt")],
+            "Symlinked abs path should be resolved into the worktree"
+        )
+    });
+    cx.dispatch_action(SelectNext);
+    cx.dispatch_action(Confirm);
+    cx.read(|cx| {
+        let active_editor = workspace.read(cx).active_item_as::<Editor>(cx).unwrap();
+        assert_eq!(active_editor.read(cx).title(cx), "file2.txt");
+    });
+}
+
 #[gpui::test]
 async fn test_complex_path(cx: &mut TestAppContext) {
     let app_state = init_test(cx);
@@ -409,6 +671,163 @@ async fn test_matching_cancellation(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_cancelled_search_keeps_previous_matches(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/dir",
+            json!({
+                "hello": "",
+                "halogen-light": "",
+                "happiness": "",
+                "height": "",
+                "hi": "",
+                "hiccup": "",
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/dir".as_ref()], cx).await;
+
+    let (picker, _, cx) = build_find_picker(project, cx);
+
+    picker
+        .update(cx, |picker, cx| {
+            picker.delegate.spawn_search(test_path_like("h"), cx)
+        })
+        .await;
+    let stable_matches = picker.update(cx, |picker, _| {
+        let matches = collect_search_matches(picker).search_matches_only();
+        assert_eq!(matches.len(), 6);
+        matches
+    });
+
+    picker.update(cx, |picker, cx| {
+        let delegate = &mut picker.delegate;
+        let query = test_path_like("hi");
+        let search_id = delegate.search_count;
+
+        // Simulate typing quickly: the search for the new query is cancelled
+        // by a yet newer one, returning only a part of its matches.
+        drop(delegate.spawn_search(query.clone(), cx));
+        drop(delegate.spawn_search(test_path_like("hic"), cx));
+        delegate.set_search_matches(
+            search_id,
+            true, // did-cancel
+            query,
+            vec![ProjectPanelOrdMatch(stable_matches[4].clone())],
+            cx,
+        );
+
+        assert_eq!(
+            collect_search_matches(picker).search_matches_only(),
+            stable_matches,
+            "Partial matches of a cancelled search for another query should not replace the shown ones"
+        );
+    });
+
+    picker
+        .update(cx, |picker, cx| {
+            picker.delegate.spawn_search(test_path_like("hic"), cx)
+        })
+        .await;
+    picker.update(cx, |picker, _| {
+        assert_eq!(
+            collect_search_matches(picker).search_paths_only(),
+            vec![PathBuf::from("hiccup")],
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_partial_matches_keep_selected_match(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/dir",
+            json!({
+                "hello": "",
+                "halogen-light": "",
+                "happiness": "",
+                "height": "",
+                "hi": "",
+                "hiccup": "",
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/dir".as_ref()], cx).await;
+
+    let (picker, _, cx) = build_find_picker(project, cx);
+
+    picker
+        .update(cx, |picker, cx| {
+            picker.delegate.spawn_search(test_path_like("h"), cx)
+        })
+        .await;
+    let all_matches = picker.update(cx, |picker, _| {
+        let matches = collect_search_matches(picker).search_matches_only();
+        assert_eq!(matches.len(), 6);
+        matches
+    });
+    let selected_path = |picker: &Picker<FileFinderDelegate>| {
+        collect_search_matches(picker).search_paths_only()[picker.delegate.selected_index()].clone()
+    };
+
+    picker.update(cx, |picker, cx| {
+        let delegate = &mut picker.delegate;
+        let query = test_path_like("hi");
+        let search_id = post_inc(&mut delegate.search_count);
+
+        // Only some of the workers have reported, the better matches of the others arrive later.
+        delegate.set_partial_matches(
+            search_id,
+            query.clone(),
+            all_matches[3..5].iter().cloned().map(ProjectPanelOrdMatch),
+            cx,
+        );
+        delegate.set_selected_index(1, cx);
+        let selected = selected_path(picker);
+        assert_eq!(selected, all_matches[4].path.to_path_buf());
+
+        let delegate = &mut picker.delegate;
+        delegate.set_partial_matches(
+            search_id,
+            query.clone(),
+            [&all_matches[0], &all_matches[3], &all_matches[4]]
+                .into_iter()
+                .cloned()
+                .map(ProjectPanelOrdMatch),
+            cx,
+        );
+        assert_eq!(
+            picker.delegate.selected_index(),
+            2,
+            "The selected match should be followed when better matches are shown above it"
+        );
+        assert_eq!(selected_path(picker), selected);
+
+        picker.delegate.set_search_matches(
+            search_id,
+            false,
+            query,
+            all_matches.iter().cloned().map(ProjectPanelOrdMatch),
+            cx,
+        );
+        assert_eq!(picker.delegate.selected_index(), 4);
+        assert_eq!(
+            selected_path(picker),
+            selected,
+            "The final matches should keep the match selected among the partial ones"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_ignored_root(cx: &mut TestAppContext) {
     let app_state = init_test(cx);
@@ -505,6 +924,64 @@ async fn test_single_file_worktrees(cx: &mut TestAppContext) {
     picker.update(cx, |f, _| assert_eq!(f.delegate.matches.len(), 0));
 }
 
+#[gpui::test]
+async fn test_labels_for_unicode_path_matches(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree("/root", json!({ "ünïcödé": { "fïlé.txt": "" } }))
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+
+    let (picker, _, cx) = build_find_picker(project, cx);
+
+    picker
+        .update(cx, |picker, cx| {
+            picker.delegate.spawn_search(test_path_like("üfl"), cx)
+        })
+        .await;
+    cx.read(|cx| {
+        let picker = picker.read(cx);
+        let delegate = &picker.delegate;
+        let matches = collect_search_matches(picker).search_matches_only();
+        assert_eq!(matches.len(), 1);
+
+        let (file_name, file_name_positions, full_path, full_path_positions) =
+            delegate.labels_for_path_match(&matches[0]);
+        assert_eq!(file_name, "fïlé.txt");
+        assert_eq!(file_name_positions, &[0, 3]);
+        assert_eq!(full_path, format!("ünïcödé{}", std::path::MAIN_SEPARATOR));
+        assert_eq!(full_path_positions, &[0]);
+
+        let (file_name, file_name_positions, full_path, full_path_positions) = delegate
+            .labels_for_path_match(&PathMatch {
+                score: 1.0,
+                positions: vec![1, 10, 12, 15, 18],
+                worktree_id: 0,
+                path: Arc::from(Path::new("dïr").join("fïlé.rs")),
+                path_prefix: Arc::from("wörktree/"),
+                distance_to_relative_ancestor: 0,
+            });
+        assert_eq!(file_name, "fïlé.rs");
+        assert_eq!(
+            file_name_positions,
+            &[0, 3],
+            "File name positions should be relative to the file name start"
+        );
+        assert_eq!(
+            full_path,
+            format!("wörktree/dïr{}", std::path::MAIN_SEPARATOR)
+        );
+        assert_eq!(
+            full_path_positions,
+            &[1, 10],
+            "Positions that are not on a char boundary should be dropped"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_path_distance_ordering(cx: &mut TestAppContext) {
     let app_state = init_test(cx);
@@ -1,10 +1,13 @@
 mod char_bag;
 mod matcher;
 mod paths;
+mod ranked_merge;
 mod strings;
 
 pub use char_bag::CharBag;
 pub use paths::{
-    match_fixed_path_set, match_path_sets, PathMatch, PathMatchCandidate, PathMatchCandidateSet,
+    match_fixed_path_set, match_path_sets, match_path_sets_incremental, CandidateBudget,
+    PartialPathMatches, PathMatch, PathMatchCandidate, PathMatchCandidateSet,
 };
+pub use ranked_merge::merge_ranked;
 pub use strings::{match_strings, StringMatch, StringMatchCandidate};
@@ -30,6 +30,11 @@ pub trait Match: Ord {
 pub trait MatchCandidate {
     fn has_chars(&self, bag: CharBag) -> bool;
     fn to_string(&self) -> Cow<'_, str>;
+    /// Returns the lowercased candidate string, if it is cached,
+    /// to avoid lowercasing the candidate on every query.
+    fn to_lowercase_string(&self) -> Option<Cow<'_, str>> {
+        None
+    }
 }
 
 impl<'a> Matcher<'a> {
@@ -66,6 +71,10 @@ impl<'a> Matcher<'a> {
         R: Match,
         F: Fn(&C, f64) -> R,
     {
+        if self.max_results == 0 {
+            return;
+        }
+
         let mut candidate_chars = Vec::new();
         let mut lowercase_candidate_chars = Vec::new();
 
@@ -80,9 +89,16 @@ impl<'a> Matcher<'a> {
 
             candidate_chars.clear();
             lowercase_candidate_chars.clear();
-            for c in candidate.to_string().chars() {
-                candidate_chars.push(c);
-                lowercase_candidate_chars.append(&mut c.to_lowercase().collect::<Vec<_>>());
+            candidate_chars.extend(candidate.to_string().chars());
+            match candidate.to_lowercase_string() {
+                Some(lowercase_candidate) => {
+                    lowercase_candidate_chars.extend(lowercase_candidate.chars())
+                }
+                None => {
+                    for c in &candidate_chars {
+                        lowercase_candidate_chars.extend(c.to_lowercase());
+                    }
+                }
             }
 
             if !self.find_last_positions(lowercase_prefix, &lowercase_candidate_chars) {
@@ -383,6 +399,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_match_path_entries_with_cached_lowercase_paths() {
+        let paths = vec![
+            "abC",
+            "abcd",
+            "AlphaBravoCharlie",
+            "/////ThisIsATestDir",
+            "/this/is/a/test/dir",
+            "aαbβ/cγdδ",
+            "ΟΔΟΣ/abc",
+        ];
+
+        for query in ["abc", "tiatd", "bcd", "οσ"] {
+            assert_eq!(
+                match_single_path_query_with_cache(query, false, &paths, true),
+                match_single_path_query(query, false, &paths),
+                "Cached lowercase paths should produce the same matches for query '{query}'"
+            );
+        }
+    }
+
     #[test]
     fn test_lowercase_longer_than_uppercase() {
         // This character has more chars in lower-case than in upper-case.
@@ -426,6 +463,15 @@ mod tests {
         query: &str,
         smart_case: bool,
         paths: &[&'a str],
+    ) -> Vec<(&'a str, Vec<usize>)> {
+        match_single_path_query_with_cache(query, smart_case, paths, false)
+    }
+
+    fn match_single_path_query_with_cache<'a>(
+        query: &str,
+        smart_case: bool,
+        paths: &[&'a str],
+        cache_lowercase_paths: bool,
     ) -> Vec<(&'a str, Vec<usize>)> {
         let lowercase_query = query.to_lowercase().chars().collect::<Vec<_>>();
         let query = query.chars().collect::<Vec<_>>();
@@ -435,13 +481,21 @@ mod tests {
             .iter()
             .map(|path| Arc::from(PathBuf::from(path)))
             .collect::<Vec<_>>();
+        let lowercase_paths = paths
+            .iter()
+            .map(|path| {
+                path.chars()
+                    .flat_map(char::to_lowercase)
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
         let mut path_entries = Vec::new();
-        for (i, path) in paths.iter().enumerate() {
-            let lowercase_path = path.to_lowercase().chars().collect::<Vec<_>>();
-            let char_bag = CharBag::from(lowercase_path.as_slice());
+        for (i, lowercase_path) in lowercase_paths.iter().enumerate() {
+            let char_bag = CharBag::from(lowercase_path.as_str());
             path_entries.push(PathMatchCandidate {
                 char_bag,
                 path: &path_arcs[i],
+                lowercase_path: cache_lowercase_paths.then_some(lowercase_path.as_str()),
             });
         }
 
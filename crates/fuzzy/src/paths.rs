@@ -3,7 +3,11 @@ use std::{
     borrow::Cow,
     cmp::{self, Ordering},
     path::Path,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{self, AtomicBool, AtomicUsize},
+        Arc, Mutex,
+    },
+    time::Duration,
 };
 
 use crate::{
@@ -14,6 +18,8 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct PathMatchCandidate<'a> {
     pub path: &'a Path,
+    /// Lowercased `path`, if it was computed ahead of time.
+    pub lowercase_path: Option<&'a str>,
     pub char_bag: CharBag,
 }
 
@@ -29,6 +35,51 @@ pub struct PathMatch {
     pub distance_to_relative_ancestor: usize,
 }
 
+/// How often each worker of [`match_path_sets_incremental`] reports the matches it found so far.
+/// Workers also report once they are done with their segment.
+const PARTIAL_MATCHES_REPORT_INTERVAL: Duration = Duration::from_millis(10);
+/// Number of candidates a worker matches between checks of [`PARTIAL_MATCHES_REPORT_INTERVAL`].
+const PARTIAL_MATCHES_BATCH_SIZE: usize = 1024;
+
+/// The best matches found so far by each worker of a running [`match_path_sets_incremental`] search.
+#[derive(Debug, Default)]
+pub struct PartialPathMatches {
+    segments: Mutex<Vec<Vec<PathMatch>>>,
+    version: AtomicUsize,
+}
+
+impl PartialPathMatches {
+    /// A counter that is incremented every time a worker reports new matches.
+    pub fn version(&self) -> usize {
+        self.version.load(atomic::Ordering::Acquire)
+    }
+
+    /// Returns the best `max_results` matches reported so far, sorted by descending score.
+    pub fn top_matches(&self, max_results: usize) -> Vec<PathMatch> {
+        let segments = self.segments.lock().unwrap();
+        let mut results = Vec::new();
+        for segment_results in segments.iter() {
+            util::extend_sorted(
+                &mut results,
+                segment_results.iter().cloned(),
+                max_results,
+                |a, b| b.cmp(a),
+            );
+        }
+        results
+    }
+
+    fn report(&self, segment_idx: usize, results: &[PathMatch]) {
+        let mut segments = self.segments.lock().unwrap();
+        if segments.len() <= segment_idx {
+            segments.resize_with(segment_idx + 1, Vec::new);
+        }
+        segments[segment_idx].clear();
+        segments[segment_idx].extend_from_slice(results);
+        self.version.fetch_add(1, atomic::Ordering::Release);
+    }
+}
+
 pub trait PathMatchCandidateSet<'a>: Send + Sync {
     type Candidates: Iterator<Item = PathMatchCandidate<'a>>;
     fn id(&self) -> usize;
@@ -38,6 +89,48 @@ pub trait PathMatchCandidateSet<'a>: Send + Sync {
     }
     fn prefix(&self) -> Arc<str>;
     fn candidates(&'a self, start: usize) -> Self::Candidates;
+    /// Limits how many of the set's candidates get matched, if any.
+    fn budget(&self) -> Option<&CandidateBudget> {
+        None
+    }
+}
+
+/// A number of candidates of a [`PathMatchCandidateSet`] to match, shared by all workers of a search.
+/// Only candidates containing all of the query's characters count towards it,
+/// the others are skipped cheaply by the matcher.
+#[derive(Debug)]
+pub struct CandidateBudget {
+    remaining: AtomicUsize,
+    exceeded: AtomicBool,
+}
+
+impl CandidateBudget {
+    pub fn new(max_candidates: usize) -> Self {
+        Self {
+            remaining: AtomicUsize::new(max_candidates),
+            exceeded: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether a search stopped matching candidates of the set because the budget ran out.
+    pub fn is_exceeded(&self) -> bool {
+        self.exceeded.load(atomic::Ordering::Relaxed)
+    }
+
+    fn take(&self) -> bool {
+        let within_budget = self
+            .remaining
+            .fetch_update(
+                atomic::Ordering::Relaxed,
+                atomic::Ordering::Relaxed,
+                |remaining| remaining.checked_sub(1),
+            )
+            .is_ok();
+        if !within_budget {
+            self.exceeded.store(true, atomic::Ordering::Relaxed);
+        }
+        within_budget
+    }
 }
 
 impl Match for PathMatch {
@@ -58,6 +151,10 @@ impl<'a> MatchCandidate for PathMatchCandidate<'a> {
     fn to_string(&self) -> Cow<'a, str> {
         self.path.to_string_lossy()
     }
+
+    fn to_lowercase_string(&self) -> Option<Cow<'a, str>> {
+        self.lowercase_path.map(Cow::Borrowed)
+    }
 }
 
 impl PartialEq for PathMatch {
@@ -135,6 +232,56 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
     max_results: usize,
     cancel_flag: &AtomicBool,
     executor: BackgroundExecutor,
+) -> Vec<PathMatch> {
+    match_path_sets_internal(
+        candidate_sets,
+        query,
+        relative_to,
+        smart_case,
+        max_results,
+        cancel_flag,
+        None,
+        executor,
+    )
+    .await
+}
+
+/// Like [`match_path_sets`], but periodically publishes the best matches found so far
+/// to `partial_matches`, so that they can be shown before the search completes.
+#[allow(clippy::too_many_arguments)]
+pub async fn match_path_sets_incremental<'a, Set: PathMatchCandidateSet<'a>>(
+    candidate_sets: &'a [Set],
+    query: &str,
+    relative_to: Option<Arc<Path>>,
+    smart_case: bool,
+    max_results: usize,
+    cancel_flag: &AtomicBool,
+    partial_matches: &PartialPathMatches,
+    executor: BackgroundExecutor,
+) -> Vec<PathMatch> {
+    match_path_sets_internal(
+        candidate_sets,
+        query,
+        relative_to,
+        smart_case,
+        max_results,
+        cancel_flag,
+        Some(partial_matches),
+        executor,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn match_path_sets_internal<'a, Set: PathMatchCandidateSet<'a>>(
+    candidate_sets: &'a [Set],
+    query: &str,
+    relative_to: Option<Arc<Path>>,
+    smart_case: bool,
+    max_results: usize,
+    cancel_flag: &AtomicBool,
+    partial_matches: Option<&PartialPathMatches>,
+    executor: BackgroundExecutor,
 ) -> Vec<PathMatch> {
     let path_count: usize = candidate_sets.iter().map(|s| s.len()).sum();
     if path_count == 0 {
@@ -154,6 +301,7 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
         .map(|_| Vec::with_capacity(max_results))
         .collect::<Vec<_>>();
 
+    let clock = &executor;
     executor
         .scoped(|scope| {
             for (segment_idx, results) in segment_results.iter_mut().enumerate() {
@@ -169,6 +317,7 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
                         max_results,
                     );
 
+                    let mut last_report = clock.now();
                     let mut tree_start = 0;
                     for candidate_set in candidate_sets {
                         let tree_end = tree_start + candidate_set.len();
@@ -176,7 +325,16 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
                         if tree_start < segment_end && segment_start < tree_end {
                             let start = cmp::max(tree_start, segment_start) - tree_start;
                             let end = cmp::min(tree_end, segment_end) - tree_start;
-                            let candidates = candidate_set.candidates(start).take(end - start);
+                            let budget = candidate_set.budget();
+                            let mut candidates = candidate_set
+                                .candidates(start)
+                                .take(end - start)
+                                .take_while(|candidate| {
+                                    budget.map_or(true, |budget| {
+                                        !candidate.char_bag.is_superset(query_char_bag)
+                                            || budget.take()
+                                    })
+                                });
 
                             let worktree_id = candidate_set.id();
                             let prefix = candidate_set.prefix().chars().collect::<Vec<_>>();
@@ -184,35 +342,62 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
                                 .iter()
                                 .map(|c| c.to_ascii_lowercase())
                                 .collect::<Vec<_>>();
-                            matcher.match_candidates(
-                                &prefix,
-                                &lowercase_prefix,
-                                candidates,
-                                results,
-                                cancel_flag,
-                                |candidate, score| PathMatch {
-                                    score,
-                                    worktree_id,
-                                    positions: Vec::new(),
-                                    path: Arc::from(candidate.path),
-                                    path_prefix: candidate_set.prefix(),
-                                    distance_to_relative_ancestor: relative_to.as_ref().map_or(
-                                        usize::MAX,
-                                        |relative_to| {
-                                            distance_between_paths(
-                                                candidate.path,
-                                                relative_to.as_ref(),
-                                            )
-                                        },
-                                    ),
-                                },
-                            );
+                            let build_match = |candidate: &PathMatchCandidate, score| PathMatch {
+                                score,
+                                worktree_id,
+                                positions: Vec::new(),
+                                path: Arc::from(candidate.path),
+                                path_prefix: candidate_set.prefix(),
+                                distance_to_relative_ancestor: relative_to.as_ref().map_or(
+                                    usize::MAX,
+                                    |relative_to| {
+                                        distance_between_paths(candidate.path, relative_to.as_ref())
+                                    },
+                                ),
+                            };
+
+                            let batch_size = if partial_matches.is_some() {
+                                PARTIAL_MATCHES_BATCH_SIZE
+                            } else {
+                                usize::MAX
+                            };
+                            loop {
+                                let mut batch = candidates.by_ref().take(batch_size).peekable();
+                                if batch.peek().is_none() {
+                                    break;
+                                }
+                                matcher.match_candidates(
+                                    &prefix,
+                                    &lowercase_prefix,
+                                    batch,
+                                    results,
+                                    cancel_flag,
+                                    build_match,
+                                );
+
+                                if cancel_flag.load(atomic::Ordering::Relaxed) {
+                                    break;
+                                }
+                                if let Some(partial_matches) = partial_matches {
+                                    let now = clock.now();
+                                    if now - last_report >= PARTIAL_MATCHES_REPORT_INTERVAL {
+                                        partial_matches.report(segment_idx, results);
+                                        last_report = now;
+                                    }
+                                }
+                            }
                         }
                         if tree_end >= segment_end {
                             break;
                         }
                         tree_start = tree_end;
                     }
+
+                    if let Some(partial_matches) = partial_matches {
+                        if !cancel_flag.load(atomic::Ordering::Relaxed) {
+                            partial_matches.report(segment_idx, results);
+                        }
+                    }
                 })
             }
         })
@@ -246,12 +431,49 @@ fn distance_between_paths(path: &Path, relative_to: &Path) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
+    use std::{path::Path, sync::Arc};
 
-    use super::distance_between_paths;
+    use super::{distance_between_paths, PartialPathMatches, PathMatch};
 
     #[test]
     fn test_distance_between_paths_empty() {
         distance_between_paths(Path::new(""), Path::new(""));
     }
+
+    #[test]
+    fn test_partial_path_matches() {
+        fn path_match(path: &str, score: f64) -> PathMatch {
+            PathMatch {
+                score,
+                positions: Vec::new(),
+                worktree_id: 0,
+                path: Arc::from(Path::new(path)),
+                path_prefix: "".into(),
+                distance_to_relative_ancestor: usize::MAX,
+            }
+        }
+        fn paths(matches: &[PathMatch]) -> Vec<&str> {
+            matches
+                .iter()
+                .map(|path_match| path_match.path.to_str().unwrap())
+                .collect()
+        }
+
+        let partial_matches = PartialPathMatches::default();
+        assert_eq!(partial_matches.version(), 0);
+        assert!(partial_matches.top_matches(3).is_empty());
+
+        partial_matches.report(1, &[path_match("b", 0.8), path_match("d", 0.2)]);
+        partial_matches.report(0, &[path_match("a", 0.9), path_match("c", 0.5)]);
+        assert_eq!(partial_matches.version(), 2);
+        assert_eq!(paths(&partial_matches.top_matches(3)), ["a", "b", "c"]);
+
+        // A worker's report replaces the matches it reported earlier.
+        partial_matches.report(1, &[path_match("e", 0.95), path_match("b", 0.8)]);
+        assert_eq!(partial_matches.version(), 3);
+        assert_eq!(
+            paths(&partial_matches.top_matches(10)),
+            ["e", "a", "b", "c"]
+        );
+    }
 }
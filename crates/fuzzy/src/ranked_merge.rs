@@ -0,0 +1,82 @@
+use std::{cmp::Ordering, collections::BTreeSet};
+
+/// Merges `items` into a list ranked by `cmp`, keeping at most `max_results` of the best ones.
+///
+/// Items with the same `dedup_key` are considered duplicates: only the first of them is kept,
+/// even if it ranks lower than the ones that follow.
+/// Items that compare equal keep their relative order from `items`.
+pub fn merge_ranked<T, K, I>(
+    items: I,
+    max_results: usize,
+    mut dedup_key: impl FnMut(&T) -> K,
+    mut cmp: impl FnMut(&T, &T) -> Ordering,
+) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    K: Ord,
+{
+    let mut seen_keys = BTreeSet::new();
+    let mut results = Vec::new();
+    for item in items {
+        if !seen_keys.insert(dedup_key(&item)) {
+            continue;
+        }
+
+        let index = results.partition_point(|result| cmp(result, &item) != Ordering::Greater);
+        if results.len() < max_results {
+            results.insert(index, item);
+        } else if index < results.len() {
+            results.pop();
+            results.insert(index, item);
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_merge_ranked() {
+        let items = [("a", 1), ("b", 3), ("a", 5), ("c", 3), ("d", 2)];
+        let merge =
+            |max_results| merge_ranked(items, max_results, |(key, _)| *key, |a, b| b.1.cmp(&a.1));
+
+        assert_eq!(merge(10), [("b", 3), ("c", 3), ("d", 2), ("a", 1)]);
+        assert_eq!(merge(2), [("b", 3), ("c", 3)]);
+        assert!(merge(0).is_empty());
+    }
+
+    #[gpui::test(iterations = 100)]
+    fn test_random_merge_ranked(mut rng: StdRng) {
+        let item_count = rng.gen_range(0..50);
+        let items = (0..item_count)
+            .map(|_| (rng.gen_range(0..20), rng.gen_range(0..10)))
+            .collect::<Vec<(u32, u32)>>();
+        let max_results = rng.gen_range(0..30);
+
+        let results = merge_ranked(
+            items.iter().copied(),
+            max_results,
+            |(key, _)| *key,
+            |a, b| b.1.cmp(&a.1),
+        );
+
+        let mut expected = Vec::<(u32, u32)>::new();
+        for item in &items {
+            if expected.iter().all(|(key, _)| *key != item.0) {
+                expected.push(*item);
+            }
+        }
+        expected.sort_by(|a, b| b.1.cmp(&a.1));
+        expected.truncate(max_results);
+
+        assert!(results.len() <= max_results);
+        assert_eq!(
+            results, expected,
+            "items: {items:?}, max_results: {max_results}"
+        );
+    }
+}
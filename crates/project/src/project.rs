@@ -7645,6 +7645,49 @@ impl Project {
         None
     }
 
+    /// Resolves an absolute path into a project path of a local worktree.
+    /// When the path does not belong to any worktree as is, it is canonicalized,
+    /// so that symlinked locations and Windows verbatim (`\\?\`) paths are mapped too.
+    pub fn resolve_abs_file_path(
+        &self,
+        abs_path: &Path,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Option<ProjectPath>> {
+        let abs_path = util::paths::strip_verbatim_prefix(abs_path);
+        if let Some((worktree, relative_path)) = self.find_local_worktree(&abs_path, cx) {
+            return Task::ready(Some(ProjectPath {
+                worktree_id: worktree.read(cx).id(),
+                path: relative_path.into(),
+            }));
+        }
+
+        let fs = self.fs.clone();
+        let worktree_roots = self
+            .worktrees()
+            .filter_map(|worktree| {
+                let worktree = worktree.read(cx);
+                Some((worktree.id(), worktree.as_local()?.abs_path().clone()))
+            })
+            .collect::<Vec<_>>();
+        cx.background_executor().spawn(async move {
+            let canonical_path = fs.canonicalize(&abs_path).await.ok()?;
+            let canonical_path = util::paths::strip_verbatim_prefix(&canonical_path);
+            for (worktree_id, worktree_root) in worktree_roots {
+                let canonical_root = match fs.canonicalize(&worktree_root).await {
+                    Ok(canonical_root) => util::paths::strip_verbatim_prefix(&canonical_root),
+                    Err(_) => worktree_root.to_path_buf(),
+                };
+                if let Ok(relative_path) = canonical_path.strip_prefix(&canonical_root) {
+                    return Some(ProjectPath {
+                        worktree_id,
+                        path: relative_path.into(),
+                    });
+                }
+            }
+            None
+        })
+    }
+
     pub fn is_shared(&self) -> bool {
         match &self.client_state {
             ProjectClientState::Shared { .. } => true,
@@ -11410,16 +11453,24 @@ impl<'a> Iterator for PathMatchCandidateSetIter<'a> {
     type Item = fuzzy::PathMatchCandidate<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.traversal.next().map(|entry| match entry.kind {
-            EntryKind::Dir => fuzzy::PathMatchCandidate {
-                path: &entry.path,
-                char_bag: CharBag::from_iter(entry.path.to_string_lossy().to_lowercase().chars()),
-            },
-            EntryKind::File(char_bag) => fuzzy::PathMatchCandidate {
-                path: &entry.path,
-                char_bag,
-            },
-            EntryKind::UnloadedDir | EntryKind::PendingDir => unreachable!(),
+        self.traversal.next().map(|entry| {
+            let lowercase_path = entry.lowercase_path.as_deref();
+            match entry.kind {
+                EntryKind::Dir => fuzzy::PathMatchCandidate {
+                    path: &entry.path,
+                    lowercase_path,
+                    char_bag: lowercase_path.map_or_else(
+                        || CharBag::from_iter(entry.path.to_string_lossy().to_lowercase().chars()),
+                        CharBag::from,
+                    ),
+                },
+                EntryKind::File(char_bag) => fuzzy::PathMatchCandidate {
+                    path: &entry.path,
+                    lowercase_path,
+                    char_bag,
+                },
+                EntryKind::UnloadedDir | EntryKind::PendingDir => unreachable!(),
+            }
         })
     }
 }
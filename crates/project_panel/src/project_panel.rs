@@ -1630,6 +1630,7 @@ impl ProjectPanel {
                         git_status: entry.git_status,
                         canonical_path: entry.canonical_path.clone(),
                         is_symlink: entry.is_symlink,
+                        lowercase_path: None,
                     });
                 }
                 if expanded_dir_ids.binary_search(&entry.id).is_err()
@@ -1,5 +1,6 @@
 use std::sync::OnceLock;
 use std::{
+    borrow::Cow,
     ffi::OsStr,
     path::{Path, PathBuf},
 };
@@ -90,6 +91,50 @@ impl<T: AsRef<Path>> PathExt for T {
     }
 }
 
+/// Strips the Windows verbatim prefix (`\\?\` or `\\?\UNC\`) from a path, if present,
+/// so that paths returned by canonicalization can be compared with the ones stored in worktrees.
+pub fn strip_verbatim_prefix(path: &Path) -> PathBuf {
+    let Some(path_str) = path.to_str() else {
+        return path.to_path_buf();
+    };
+    if let Some(unc_path) = path_str.strip_prefix(r"\\?\UNC\") {
+        PathBuf::from(format!(r"\\{unc_path}"))
+    } else if let Some(local_path) = path_str.strip_prefix(r"\\?\") {
+        PathBuf::from(local_path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Normalizes a path string for display, leaving the underlying path untouched.
+///
+/// On Windows, `/` separators are replaced with `\` and a lowercase drive letter is uppercased,
+/// so that paths assembled from different sources render consistently.
+/// The result always has the same byte length as the input, so byte offsets into it stay valid.
+pub fn normalize_path_for_display(path: &str) -> Cow<'_, str> {
+    if cfg!(windows) {
+        normalize_windows_path_for_display(path)
+    } else {
+        Cow::Borrowed(path)
+    }
+}
+
+fn normalize_windows_path_for_display(path: &str) -> Cow<'_, str> {
+    let lowercase_drive_letter = matches!(
+        path.as_bytes(),
+        [drive_letter, b':', ..] if drive_letter.is_ascii_lowercase()
+    );
+    if !lowercase_drive_letter && !path.contains('/') {
+        return Cow::Borrowed(path);
+    }
+
+    let mut normalized = path.replace('/', "\\");
+    if lowercase_drive_letter {
+        normalized[..1].make_ascii_uppercase();
+    }
+    Cow::Owned(normalized)
+}
+
 /// A delimiter to use in `path_query:row_number:column_number` strings parsing.
 pub const FILE_ROW_COLUMN_DELIMITER: char = ':';
 
@@ -601,4 +646,39 @@ mod tests {
             "Path matcher should match {path:?}"
         );
     }
+
+    #[test]
+    fn test_strip_verbatim_prefix() {
+        assert_eq!(
+            strip_verbatim_prefix(Path::new(r"\\?\C:\Users\someone\test_file.rs")),
+            PathBuf::from(r"C:\Users\someone\test_file.rs")
+        );
+        assert_eq!(
+            strip_verbatim_prefix(Path::new(r"\\?\UNC\server\share\test_file.rs")),
+            PathBuf::from(r"\\server\share\test_file.rs")
+        );
+        assert_eq!(
+            strip_verbatim_prefix(Path::new("/home/someone/test_file.rs")),
+            PathBuf::from("/home/someone/test_file.rs")
+        );
+    }
+
+    #[test]
+    fn test_normalize_windows_path_for_display() {
+        assert_eq!(
+            normalize_windows_path_for_display(r"c:\Users\someone/project/src/main.rs"),
+            r"C:\Users\someone\project\src\main.rs"
+        );
+        assert_eq!(
+            normalize_windows_path_for_display("project/src/main.rs"),
+            r"project\src\main.rs"
+        );
+        assert!(matches!(
+            normalize_windows_path_for_display(r"D:\project\src\main.rs"),
+            Cow::Borrowed(r"D:\project\src\main.rs")
+        ));
+
+        let path = "d:/проект/src/main.rs";
+        assert_eq!(normalize_windows_path_for_display(path).len(), path.len());
+    }
 }
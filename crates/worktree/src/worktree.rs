@@ -3128,6 +3128,9 @@ pub struct Entry {
     pub git_status: Option<GitFileStatus>,
     /// Whether this entry is considered to be a `.env` file.
     pub is_private: bool,
+    /// The lowercased path of this entry, cached for fuzzy matching.
+    /// `None` if it wasn't computed, in which case the matcher lowercases the path itself.
+    pub lowercase_path: Option<Arc<str>>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -3177,6 +3180,7 @@ impl Entry {
             } else {
                 EntryKind::File(char_bag_for_path(root_char_bag, &path))
             },
+            lowercase_path: Some(lowercase_path(&path)),
             path,
             inode: metadata.inode,
             mtime: Some(metadata.mtime),
@@ -4641,6 +4645,15 @@ fn char_bag_for_path(root_char_bag: CharBag, path: &Path) -> CharBag {
     result
 }
 
+/// Lowercases the path a `char` at a time, the same way the matcher does for paths without a cached lowercase version.
+fn lowercase_path(path: &Path) -> Arc<str> {
+    path.to_string_lossy()
+        .chars()
+        .flat_map(char::to_lowercase)
+        .collect::<String>()
+        .into()
+}
+
 struct ScanJob {
     abs_path: Arc<Path>,
     path: Arc<Path>,
@@ -5065,6 +5078,7 @@ impl<'a> TryFrom<(&'a CharBag, proto::Entry)> for Entry {
         Ok(Entry {
             id: ProjectEntryId::from_proto(entry.id),
             kind,
+            lowercase_path: Some(lowercase_path(&path)),
             path,
             inode: entry.inode,
             mtime: entry.mtime.map(|time| time.into()),